@@ -3,12 +3,12 @@ use anyhow::Result;
 use crate::config::Config;
 use crate::entry::Entry;
 use crate::storage;
-use crate::util;
+use crate::util::ClockTz;
 
 /// Execute the add command
-pub fn execute(cli: &crate::Cli, config: &Config, name: &str, comment: Option<&str>) -> Result<()> {
+pub fn execute(cli: &crate::Cli, config: &Config, clock: &ClockTz, name: &str, comment: Option<&str>) -> Result<()> {
     // Determine current time, either from command line or system
-    let now = util::parse_now_arg(cli.now.as_deref())?;
+    let now = clock.parse_now(cli.now.as_deref())?;
     
     // Create an entry
     let entry = Entry::new(now, name.to_string(), false, comment.map(|s| s.to_string()));
@@ -20,7 +20,7 @@ pub fn execute(cli: &crate::Cli, config: &Config, name: &str, comment: Option<&s
     crate::config::ensure_data_dir(&data_file)?;
     
     // Write the entry to the log file
-    storage::append_entry(&data_file, &entry)?;
+    storage::append_entry(&data_file, &entry, clock)?;
     
     Ok(())
 }
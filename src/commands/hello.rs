@@ -1,15 +1,15 @@
 use anyhow::Result;
-use chrono::{Local, NaiveTime, TimeZone};
+use chrono::NaiveTime;
 
 use crate::config::Config;
 use crate::entry::{Entry, HELLO_ENTRY_NAME, MIDNIGHT_SEPARATOR_PREFIX};
 use crate::storage;
-use crate::util;
+use crate::util::ClockTz;
 
 /// Execute the hello command
-pub fn execute(cli: &crate::Cli, config: &Config) -> Result<()> {
+pub fn execute(cli: &crate::Cli, config: &Config, clock: &ClockTz) -> Result<()> {
     // Determine current time, either from command line or system
-    let now = util::parse_now_arg(cli.now.as_deref())?;
+    let now = clock.parse_now(cli.now.as_deref())?;
     
     // Get data file path
     let data_file = cli.data.as_ref().map(std::path::PathBuf::from).unwrap_or_else(|| config.data_file.clone());
@@ -23,8 +23,8 @@ pub fn execute(cli: &crate::Cli, config: &Config) -> Result<()> {
     // Check if we need to create a midnight separator
     let create_midnight_separator = if !existing_entries.is_empty() {
         let last_entry = existing_entries.last().unwrap();
-        let last_entry_date = last_entry.datetime.date_naive();
-        let current_date = now.date_naive();
+        let last_entry_date = clock.today(last_entry.datetime);
+        let current_date = clock.today(now);
         
         // If the last entry is from a previous day, we should add a midnight separator
         last_entry_date < current_date
@@ -35,8 +35,8 @@ pub fn execute(cli: &crate::Cli, config: &Config) -> Result<()> {
     // If needed, add a midnight separator entry
     if create_midnight_separator {
         // Create a midnight entry at 00:00 of the current day
-        let midnight_naive = now.date_naive().and_time(NaiveTime::from_hms_opt(0, 0, 0).unwrap());
-        let midnight = Local.from_local_datetime(&midnight_naive).single().unwrap();
+        let midnight_naive = clock.today(now).and_time(NaiveTime::from_hms_opt(0, 0, 0).unwrap());
+        let midnight = clock.from_naive(midnight_naive)?;
         
         // Create a midnight separator entry
         let midnight_entry = Entry::new(
@@ -47,14 +47,14 @@ pub fn execute(cli: &crate::Cli, config: &Config) -> Result<()> {
         );
         
         // Write the midnight separator to the log file
-        storage::append_entry(&data_file, &midnight_entry)?;
+        storage::append_entry(&data_file, &midnight_entry, clock)?;
     }
     
     // Create a hello entry
     let entry = Entry::new(now, HELLO_ENTRY_NAME.to_string(), false, None);
     
     // Write the entry to the log file
-    storage::append_entry(&data_file, &entry)?;
+    storage::append_entry(&data_file, &entry, clock)?;
     
     Ok(())
 }
@@ -0,0 +1,72 @@
+use anyhow::{anyhow, Result};
+use chrono::Duration;
+
+use crate::config::Config;
+use crate::entry::{Entry, HELLO_ENTRY_NAME, IDLE_SEPARATOR_PREFIX, MIDNIGHT_SEPARATOR_PREFIX};
+use crate::storage;
+use crate::util::ClockTz;
+
+/// Execute the idle command
+///
+/// Rewrites the log, inserting an explicit `** idle` separator `threshold`
+/// after every entry whose span to the next entry is longer than the
+/// threshold, so that the rest of that span no longer counts as work in
+/// reports.
+pub fn execute(cli: &crate::Cli, config: &Config, clock: &ClockTz, threshold: Option<i64>) -> Result<()> {
+    // Resolve the threshold from the flag or config default
+    let minutes = threshold
+        .or(config.idle_threshold_minutes)
+        .ok_or_else(|| anyhow!("No idle threshold given (use --threshold or set idle_threshold_minutes)"))?;
+    let threshold = Duration::minutes(minutes);
+
+    // Get data file path
+    let data_file = cli.data.as_ref().map(std::path::PathBuf::from).unwrap_or_else(|| config.data_file.clone());
+
+    // Read all entries from the log file
+    let entries = storage::read_entries(&data_file)?;
+
+    if entries.len() < 2 {
+        return Ok(());
+    }
+
+    // Build a new entry list with idle separators inserted at gap boundaries
+    let mut rewritten = Vec::with_capacity(entries.len());
+    let mut inserted = 0;
+
+    for i in 0..entries.len() {
+        rewritten.push(entries[i].clone());
+
+        if i + 1 >= entries.len() {
+            break;
+        }
+
+        // Never split across markers that don't represent real work spans
+        if entries[i].name == HELLO_ENTRY_NAME
+            || entries[i].name.starts_with(MIDNIGHT_SEPARATOR_PREFIX)
+            || entries[i].name.starts_with(IDLE_SEPARATOR_PREFIX)
+        {
+            continue;
+        }
+
+        let gap = entries[i + 1].datetime.signed_duration_since(entries[i].datetime);
+        if gap > threshold {
+            // Start the separator `threshold` after the task entry, not at the
+            // same instant, so the preceding task keeps a real (non-zero)
+            // duration instead of collapsing into a spurious 0h00 activity.
+            let idle = Entry::new(
+                entries[i].datetime + threshold,
+                IDLE_SEPARATOR_PREFIX.to_string(),
+                false,
+                None,
+            );
+            rewritten.push(idle);
+            inserted += 1;
+        }
+    }
+
+    storage::write_entries(&data_file, &rewritten, clock)?;
+
+    println!("inserted {} idle separator(s)", inserted);
+
+    Ok(())
+}
@@ -2,14 +2,15 @@ use anyhow::{anyhow, Result};
 use chrono::{DateTime, Local};
 
 use crate::config::Config;
-use crate::report::{self, ReportOptions, ReportRange};
+use crate::report::{self, ReportFormat, ReportOptions, ReportRange};
 use crate::storage;
-use crate::util;
+use crate::util::{self, ClockTz};
 
 /// Execute the report command
 pub fn execute(
     cli: &crate::Cli,
     config: &Config,
+    clock: &ClockTz,
     date: Option<&str>,
     current_activity: &str,
     no_current_activity: bool,
@@ -18,65 +19,78 @@ pub fn execute(
     project: Option<&str>,
     _per_day: bool, // Unused parameter, renamed with underscore
     csv_section: Option<&str>,
+    json: bool,
+    markdown: bool,
+    chart: bool,
+    billing: bool,
+    round: Option<i64>,
+    round_mode: Option<&str>,
+    idle_threshold: Option<i64>,
     month: Option<&str>,
     week: Option<&str>,
     details: bool,
     comments: bool,
 ) -> Result<()> {
     // Determine current time
-    let now = util::parse_now_arg(cli.now.as_deref())?;
+    let now = clock.parse_now(cli.now.as_deref())?;
+
+    // Resolve the idle threshold from the flag or config default
+    let idle_threshold = idle_threshold
+        .or(config.idle_threshold_minutes)
+        .map(chrono::Duration::minutes);
     
     // Parse report date range
-    let range = parse_date_range(date, from_date, to_date, month, week, &now)?;
-    
+    let range = parse_date_range(date, from_date, to_date, month, week, clock, &now)?;
+
     // Get data file path
     let data_file = cli.data.as_ref().map(std::path::PathBuf::from).unwrap_or_else(|| config.data_file.clone());
-    
+
     // Read all entries from the log file
     let all_entries = storage::read_entries(&data_file)?;
-    
+
     // Check if we're displaying just today's report (the default case)
-    let is_today_only = range.start_date == now.date_naive() && 
-                         range.end_date == now.date_naive() && 
-                         date.is_none() && from_date.is_none() && 
-                         to_date.is_none() && month.is_none() && 
+    let today = clock.today(now);
+    let is_today_only = range.start_date == today &&
+                         range.end_date == today &&
+                         date.is_none() && from_date.is_none() &&
+                         to_date.is_none() && month.is_none() &&
                          week.is_none();
-    
+
     // Filter entries by date range considering midnight separators
     let filtered_entries = storage::filter_entries_by_date_range(
-        &all_entries, 
-        range.start_date, 
-        range.end_date
+        &all_entries,
+        range.start_date,
+        range.end_date,
+        clock,
     );
-    
+
     // Convert entries to activities
-    let mut activities = storage::entries_to_activities(&filtered_entries, Some(range.start_date), Some(range.end_date), Some(now));    
-    
+    let mut activities = storage::entries_to_activities(&filtered_entries, Some(range.start_date), Some(range.end_date), Some(now), idle_threshold, clock);
+
     // For today-only reports, ensure we show all activities that have a start or end time today
     if is_today_only {
         activities.retain(|activity| {
-            let today = now.date_naive();
-            let activity_date = activity.end.date_naive();
-            
+            let activity_date = clock.today(activity.end);
+
             // For current day reports, show activities that end today
             activity_date == today
         });
     } else {
         // For date range reports, show all activities that occur within the range
         activities.retain(|activity| {
-            let activity_date = activity.end.date_naive();
-            
+            let activity_date = clock.today(activity.end);
+
             // Include activities where the end date falls within the range
             activity_date >= range.start_date && activity_date <= range.end_date
         });
     }
-    
+
     // Add current activity if requested
     if !no_current_activity && !filtered_entries.is_empty() {
         let last_entry = filtered_entries.last().unwrap();
-        
+
         // Only add current activity if the last entry is from today
-        if last_entry.datetime.date_naive() == now.date_naive() {
+        if clock.today(last_entry.datetime) == today {
             let current_activity_name = if current_activity.is_empty() {
                 "-- Current Activity --"
             } else {
@@ -93,20 +107,47 @@ pub fn execute(
         }
     }
     
+    // Compute the work-hours target for the range, scaling the daily target
+    // (or a fifth of the weekly target) across the covered working days.
+    let work_target = config.daily_target_hours
+        .or(config.weekly_target_hours.map(|w| w / 5.0))
+        .map(|daily_hours| {
+            let working_days = util::count_working_days(range.start_date, range.end_date).max(0);
+            let hours = daily_hours * working_days as f64;
+            chrono::Duration::seconds((hours * 3600.0) as i64)
+        });
+
     // Create report options
     let options = ReportOptions {
         range,
         project_filter: project.map(|s| s.to_string()),
         csv_section: csv_section.map(|s| s.to_string()),
+        format: if json {
+            ReportFormat::Json
+        } else if markdown {
+            ReportFormat::Markdown
+        } else {
+            ReportFormat::Text
+        },
         show_details: details,
         show_comments: comments,
+        billing,
+        rates: config.rates.clone(),
+        round_minutes: round.or(config.round_minutes),
+        round_mode: util::RoundMode::parse(round_mode.or(config.round_mode.as_deref())),
+        chart,
+        work_target,
     };
     
     // Generate the report
     let report = if csv_section.is_some() {
         report::generate_csv_report(&activities, &options)?
     } else {
-        report::generate_report(&activities, &options)?
+        match options.format {
+            ReportFormat::Json => report::generate_json_report(&activities, &options)?,
+            ReportFormat::Markdown => report::generate_markdown_report(&activities, &options)?,
+            ReportFormat::Text => report::generate_report(&activities, &options)?,
+        }
     };
     
     // Print the report
@@ -122,10 +163,11 @@ fn parse_date_range(
     to_date: Option<&str>,
     month: Option<&str>,
     week: Option<&str>,
+    clock: &ClockTz,
     now: &DateTime<Local>,
 ) -> Result<ReportRange> {
-    let today = now.date_naive();
-    
+    let today = clock.today(*now);
+
     // First, determine the initial range based on date, month, or week
     let (mut start_date, mut end_date) = if let Some(month_str) = month {
         // Month range
@@ -134,22 +176,26 @@ fn parse_date_range(
         // Week range
         util::parse_week(week_str, today)?
     } else if let Some(date_str) = date {
-        // Single day
-        let report_date = util::parse_date_string(date_str, now, true)?;
-        (report_date, report_date)
+        // Natural-language range (e.g. "last week"/"last month") or a single day
+        if let Some(range) = util::parse_relative_range(date_str, today) {
+            range
+        } else {
+            let report_date = util::parse_date_string(date_str, clock, now, true)?;
+            (report_date, report_date)
+        }
     } else {
         // Default to today
         (today, today)
     };
-    
+
     // Override start date if specified
     if let Some(from_str) = from_date {
-        start_date = util::parse_date_string(from_str, now, true)?;
+        start_date = util::parse_date_string(from_str, clock, now, true)?;
     }
-    
+
     // Override end date if specified
     if let Some(to_str) = to_date {
-        end_date = util::parse_date_string(to_str, now, false)?;
+        end_date = util::parse_date_string(to_str, clock, now, false)?;
     }
     
     // Make sure start date is not after end date
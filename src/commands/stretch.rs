@@ -3,12 +3,12 @@ use anyhow::{anyhow, Result};
 use crate::config::Config;
 use crate::entry::Entry;
 use crate::storage;
-use crate::util;
+use crate::util::ClockTz;
 
 /// Execute the stretch command
-pub fn execute(cli: &crate::Cli, config: &Config) -> Result<()> {
+pub fn execute(cli: &crate::Cli, config: &Config, clock: &ClockTz) -> Result<()> {
     // Determine current time, either from command line or system
-    let now = util::parse_now_arg(cli.now.as_deref())?;
+    let now = clock.parse_now(cli.now.as_deref())?;
     
     // Get data file path
     let data_file = cli.data.as_ref().map(std::path::PathBuf::from).unwrap_or_else(|| config.data_file.clone());
@@ -33,7 +33,7 @@ pub fn execute(cli: &crate::Cli, config: &Config) -> Result<()> {
     );
     
     // Write the new entry to the log file
-    storage::append_entry(&data_file, &new_entry)?;
+    storage::append_entry(&data_file, &new_entry, clock)?;
     
     // Output the information
     println!("stretched {} {}", latest_entry.datetime.format("%Y-%m-%d %H:%M"), latest_entry.name);
@@ -1,11 +1,27 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 const CONFIG_FILENAME: &str = "tt.json";
 const DATA_FILENAME: &str = "entries.log";
 
+/// An hourly billing rate for a project.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProjectRate {
+    /// Hourly rate, in units of `currency`.
+    pub rate: f64,
+
+    /// Currency label shown in invoice output (e.g. "EUR", "$").
+    #[serde(default = "default_currency")]
+    pub currency: String,
+}
+
+fn default_currency() -> String {
+    String::from("USD")
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Config {
     /// Path to the entries data file
@@ -13,7 +29,43 @@ pub struct Config {
     
     /// Whether timezone support is enabled
     pub timezone_enabled: bool,
-    
+
+    /// Default clock timezone (IANA name, e.g. "Europe/Rome"). When set and
+    /// `timezone_enabled` is true, day boundaries and `--now` are interpreted in
+    /// this zone unless overridden by `--timezone`; timestamps in the log and in
+    /// report output keep the system local offset.
+    #[serde(default)]
+    pub timezone: Option<String>,
+
+    /// Per-project hourly billing rates, keyed by project name.
+    #[serde(default)]
+    pub rates: HashMap<String, ProjectRate>,
+
+    /// Default rounding increment in minutes (e.g. 6, 15, 30) applied to report
+    /// and CSV durations, and to billing (which always rounds up).
+    #[serde(default)]
+    pub round_minutes: Option<i64>,
+
+    /// Default rounding mode for report durations ("nearest", "up", "down").
+    #[serde(default)]
+    pub round_mode: Option<String>,
+
+    /// Wall-clock gap (in minutes) beyond which a span between two entries is
+    /// treated as idle rather than credited to the task, keeping totals honest
+    /// when `tt` wasn't run over a break.
+    #[serde(default)]
+    pub idle_threshold_minutes: Option<i64>,
+
+    /// Target working hours for a single day, used to show progress in the
+    /// report summary.
+    #[serde(default)]
+    pub daily_target_hours: Option<f64>,
+
+    /// Target working hours for a full week. Falls back to five daily targets
+    /// when only `daily_target_hours` is set.
+    #[serde(default)]
+    pub weekly_target_hours: Option<f64>,
+
     /// Default editor for editing entries
     pub editor: String,
 }
@@ -27,6 +79,13 @@ impl Default for Config {
         Config {
             data_file: data_dir.join(DATA_FILENAME),
             timezone_enabled: false,
+            timezone: None,
+            rates: HashMap::new(),
+            round_minutes: None,
+            round_mode: None,
+            idle_threshold_minutes: None,
+            daily_target_hours: None,
+            weekly_target_hours: None,
             editor: std::env::var("EDITOR")
                 .or_else(|_| std::env::var("VISUAL"))
                 .unwrap_or_else(|_| String::from("vi")),
@@ -8,6 +8,11 @@ use std::hash::{Hash, Hasher};
 // Constants for activity types
 pub const HELLO_ENTRY_NAME: &str = "hello";
 
+/// Name marking an explicit idle/break gap inserted either automatically during
+/// report construction or by the `idle` subcommand when a long wall-clock gap
+/// is found between two entries.
+pub const IDLE_SEPARATOR_PREFIX: &str = "** idle";
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ActivityType {
     Work,
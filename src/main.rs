@@ -77,6 +77,34 @@ enum Commands {
         /// Output a CSV report instead of text
         #[arg(long)]
         csv_section: Option<String>,
+
+        /// Output the full report as JSON instead of text
+        #[arg(long)]
+        json: bool,
+
+        /// Output the full report as Markdown tables instead of text
+        #[arg(long)]
+        markdown: bool,
+
+        /// Show bar-chart visualizations of time per project and per day
+        #[arg(long)]
+        chart: bool,
+
+        /// Show a per-project invoice using configured hourly rates
+        #[arg(long)]
+        billing: bool,
+
+        /// Round durations to this many minutes (e.g. 6, 15, 30); billing always rounds up
+        #[arg(long)]
+        round: Option<i64>,
+
+        /// Rounding mode for report durations: nearest, up or down
+        #[arg(long)]
+        round_mode: Option<String>,
+
+        /// Treat gaps longer than this many minutes as idle instead of work
+        #[arg(long)]
+        idle_threshold: Option<i64>,
         
         /// Specify a month (YYYY-MM, month name, 'this', 'prev')
         #[arg(long)]
@@ -97,6 +125,13 @@ enum Commands {
     
     /// Stretch the latest task to the current time
     Stretch,
+
+    /// Insert explicit '** idle' separators where long gaps exist in the log
+    Idle {
+        /// Gap threshold in minutes (defaults to the configured value)
+        #[arg(long)]
+        threshold: Option<i64>,
+    },
     
     /// Show or modify configuration
     Config {
@@ -123,13 +158,22 @@ fn main() {
         }
     };
     
+    // Resolve the clock timezone from the global flag and configuration
+    let clock = match util::ClockTz::resolve(cli.timezone.as_deref(), &config) {
+        Ok(clock) => clock,
+        Err(e) => {
+            eprintln!("Error resolving timezone: {}", e);
+            process::exit(1);
+        }
+    };
+
     // Execute the appropriate command
     let result = match &cli.command {
         Commands::Hello => {
-            commands::hello::execute(&cli, &config)
+            commands::hello::execute(&cli, &config, &clock)
         },
         Commands::Add { name, comment } => {
-            commands::add::execute(&cli, &config, name, comment.as_deref())
+            commands::add::execute(&cli, &config, &clock, name, comment.as_deref())
         },
         Commands::Edit => {
             commands::edit::execute(&cli, &config)
@@ -141,16 +185,24 @@ fn main() {
             from, 
             to, 
             project, 
-            per_day, 
-            csv_section, 
-            month, 
-            week, 
-            details, 
-            comments 
+            per_day,
+            csv_section,
+            json,
+            markdown,
+            chart,
+            billing,
+            round,
+            round_mode,
+            idle_threshold,
+            month,
+            week,
+            details,
+            comments
         } => {
             commands::report::execute(
                 &cli,
                 &config,
+                &clock,
                 date.as_deref(),
                 current_activity,
                 *no_current_activity,
@@ -159,6 +211,13 @@ fn main() {
                 project.as_deref(),
                 *per_day,
                 csv_section.as_deref(),
+                *json,
+                *markdown,
+                *chart,
+                *billing,
+                *round,
+                round_mode.as_deref(),
+                *idle_threshold,
                 month.as_deref(),
                 week.as_deref(),
                 *details,
@@ -166,7 +225,10 @@ fn main() {
             )
         },
         Commands::Stretch => {
-            commands::stretch::execute(&cli, &config)
+            commands::stretch::execute(&cli, &config, &clock)
+        },
+        Commands::Idle { threshold } => {
+            commands::idle::execute(&cli, &config, &clock, *threshold)
         },
         Commands::Config { default, filename } => {
             commands::config::execute(&cli, &config, *default, *filename)
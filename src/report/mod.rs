@@ -1,7 +1,9 @@
 use anyhow::Result;
-use chrono::NaiveDate;
+use chrono::{DateTime, Local, NaiveDate};
+use serde::Serialize;
 use std::collections::{HashMap, HashSet};
 
+use crate::config::ProjectRate;
 use crate::entry::{Activity, ActivityType};
 use crate::util;
 
@@ -10,12 +12,44 @@ pub struct ReportRange {
     pub end_date: NaiveDate,
 }
 
+/// Structural output format for the report command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Text,
+    Json,
+    Markdown,
+}
+
 pub struct ReportOptions {
     pub range: ReportRange,
     pub project_filter: Option<String>,
     pub csv_section: Option<String>,
+    pub format: ReportFormat,
     pub show_details: bool,
     pub show_comments: bool,
+    pub billing: bool,
+    pub rates: HashMap<String, ProjectRate>,
+    pub round_minutes: Option<i64>,
+    pub round_mode: util::RoundMode,
+    pub chart: bool,
+    pub work_target: Option<chrono::Duration>,
+}
+
+/// Return a copy of the activities with each duration snapped to the configured
+/// rounding increment, so summary, project and activity totals all stay
+/// consistent with the per-entry values shown. Returns the input unchanged when
+/// no rounding increment is set.
+fn round_activities(activities: &[Activity], options: &ReportOptions) -> Vec<Activity> {
+    match options.round_minutes {
+        Some(minutes) => activities.iter()
+            .map(|a| {
+                let mut rounded = a.clone();
+                rounded.duration = util::round_duration(a.duration, minutes, options.round_mode);
+                rounded
+            })
+            .collect(),
+        None => activities.to_vec(),
+    }
 }
 
 pub fn generate_report(activities: &[Activity], options: &ReportOptions) -> Result<String> {
@@ -49,36 +83,215 @@ pub fn generate_report(activities: &[Activity], options: &ReportOptions) -> Resu
         })
         .cloned()
         .collect();
-    
+
+    // Snap durations to the configured rounding increment so the summary,
+    // project and activity totals agree with the per-entry values.
+    let rounded_activities = round_activities(&filtered_activities, options);
+
     // Calculate summary
-    let summary = calculate_summary(&filtered_activities);
-    output.push_str(&format_summary(&summary));
+    let summary = calculate_summary(&rounded_activities);
+    output.push_str(&format_summary(&summary, options.work_target));
     output.push_str("\n\n");
-    
+
     // Generate projects section
-    let projects = group_by_project(&filtered_activities);
+    let projects = group_by_project(&rounded_activities);
     output.push_str(&util::format_title("Projects"));
     output.push_str("\n\n");
     output.push_str(&format_projects(&projects));
     output.push_str("\n\n");
-    
+
     // Generate activities section
-    let activity_groups = group_by_activity(&filtered_activities);
+    let activity_groups = group_by_activity(&rounded_activities);
     output.push_str(&util::format_title("Activities"));
     output.push_str("\n\n");
     output.push_str(&format_activity_groups(&activity_groups));
     output.push_str("\n\n");
-    
+
     // Generate details section if it's a single day report or details are explicitly requested
     if options.range.start_date == options.range.end_date || options.show_details {
         output.push_str(&util::format_title("Details"));
         output.push_str("\n\n");
-        output.push_str(&format_details(&filtered_activities, options.show_comments));
+        output.push_str(&format_details(&rounded_activities, options.show_comments));
     }
-    
+
+    // Generate bar-chart visualizations if requested
+    if options.chart {
+        output.push_str("\n\n");
+        output.push_str(&util::format_title("Charts"));
+        output.push_str("\n\n");
+        output.push_str(&format_charts(&rounded_activities));
+    }
+
+    // Generate the invoice section if billing is requested
+    if options.billing {
+        output.push_str("\n\n");
+        output.push_str(&util::format_title("Invoice"));
+        output.push_str("\n\n");
+        output.push_str(&format_billing(&filtered_activities, options));
+    }
+
     Ok(output)
 }
 
+/// Render horizontal bar charts of time per project and per day, reusing the
+/// already-computed aggregates and scaling each bar to the terminal width.
+fn format_charts(activities: &[Activity]) -> String {
+    let mut output = String::new();
+
+    // Per-project chart (work activities only).
+    let projects = group_by_project(activities);
+    let mut project_rows: Vec<(String, chrono::Duration)> = projects.iter()
+        .map(|(name, (duration, _))| {
+            let label = if name.is_empty() { "(no project)".to_string() } else { name.clone() };
+            (label, *duration)
+        })
+        .collect();
+    project_rows.sort_by(|a, b| b.1.cmp(&a.1));
+
+    if !project_rows.is_empty() {
+        output.push_str("By project:\n\n");
+        output.push_str(&render_chart(&project_rows));
+        output.push('\n');
+    }
+
+    // Per-day chart (work + break).
+    let mut days: HashMap<NaiveDate, chrono::Duration> = HashMap::new();
+    for activity in activities {
+        if activity.activity_type == ActivityType::Ignored {
+            continue;
+        }
+        let date = activity.end.date_naive();
+        let entry = days.entry(date).or_insert_with(chrono::Duration::zero);
+        *entry = *entry + activity.duration;
+    }
+    let mut day_dates: Vec<NaiveDate> = days.keys().cloned().collect();
+    day_dates.sort();
+    let day_rows: Vec<(String, chrono::Duration)> = day_dates.iter()
+        .map(|date| (date.format("%Y-%m-%d").to_string(), days[date]))
+        .collect();
+
+    if day_rows.len() > 1 {
+        output.push_str("By day:\n\n");
+        output.push_str(&render_chart(&day_rows));
+    }
+
+    output
+}
+
+/// Render a set of labelled bars, aligning labels and scaling the longest bar
+/// to the columns left over after the label and duration text.
+fn render_chart(rows: &[(String, chrono::Duration)]) -> String {
+    let mut output = String::new();
+
+    let max_duration = rows.iter().map(|(_, d)| d.num_seconds()).max().unwrap_or(0);
+    if max_duration == 0 {
+        return output;
+    }
+
+    let label_width = rows.iter().map(|(label, _)| label.chars().count()).max().unwrap_or(0);
+    // Reserve room for label, the formatted duration and separators.
+    let bar_width = util::terminal_width()
+        .saturating_sub(label_width + 12)
+        .max(10);
+
+    for (label, duration) in rows {
+        let fraction = duration.num_seconds() as f64 / max_duration as f64;
+        let bar = util::render_bar(fraction, bar_width);
+        output.push_str(&format!(
+            "{:<width$} {} {}\n",
+            label,
+            bar,
+            util::format_duration(*duration),
+            width = label_width,
+        ));
+    }
+
+    output
+}
+
+/// Render a per-project invoice: hours worked, rounded hours, and the amount
+/// owed at each project's configured rate, followed by a grand total per
+/// currency. Only `Work` activities are billed; each activity's duration is
+/// rounded up to `round_minutes` (defaulting to the config value) before
+/// costing.
+fn format_billing(activities: &[Activity], options: &ReportOptions) -> String {
+    let round_minutes = options.round_minutes.unwrap_or(0);
+
+    // Accumulate per project: raw duration, rounded duration, amount, currency.
+    let mut projects: HashMap<String, (chrono::Duration, chrono::Duration, f64, Option<String>)> = HashMap::new();
+
+    for activity in activities {
+        if activity.activity_type != ActivityType::Work {
+            continue;
+        }
+
+        let project_name = activity.project.clone().unwrap_or_default();
+        let rounded = util::round_duration_up(activity.duration, round_minutes);
+        let rate = options.rates.get(&project_name);
+        let amount = rate
+            .map(|r| rounded.num_seconds() as f64 / 3600.0 * r.rate)
+            .unwrap_or(0.0);
+        let currency = rate.map(|r| r.currency.clone());
+
+        let entry = projects
+            .entry(project_name)
+            .or_insert((chrono::Duration::zero(), chrono::Duration::zero(), 0.0, currency.clone()));
+        entry.0 = entry.0 + activity.duration;
+        entry.1 = entry.1 + rounded;
+        entry.2 += amount;
+        if entry.3.is_none() {
+            entry.3 = currency;
+        }
+    }
+
+    let mut output = String::new();
+
+    let mut project_names: Vec<&String> = projects.keys().collect();
+    project_names.sort();
+
+    // Totals grouped by currency.
+    let mut totals: HashMap<String, f64> = HashMap::new();
+
+    for project_name in project_names {
+        let (raw, rounded, amount, currency) = &projects[project_name];
+        let rounded_hours = rounded.num_seconds() as f64 / 3600.0;
+        let label = if project_name.is_empty() { "(no project)" } else { project_name };
+
+        match currency {
+            Some(currency) => {
+                *totals.entry(currency.clone()).or_insert(0.0) += amount;
+                output.push_str(&format!(
+                    "{}: {} ({:.2}h rounded) = {:.2} {}\n",
+                    label,
+                    util::format_duration(*raw),
+                    rounded_hours,
+                    amount,
+                    currency,
+                ));
+            },
+            None => {
+                output.push_str(&format!(
+                    "{}: {} ({:.2}h rounded) = no rate configured\n",
+                    label,
+                    util::format_duration(*raw),
+                    rounded_hours,
+                ));
+            },
+        }
+    }
+
+    if !totals.is_empty() {
+        output.push('\n');
+        let mut currencies: Vec<&String> = totals.keys().collect();
+        currencies.sort();
+        for currency in currencies {
+            output.push_str(&format!("Total: {:.2} {}\n", totals[currency], currency));
+        }
+    }
+
+    output
+}
+
 struct Summary {
     total_time: chrono::Duration,
     work_time: chrono::Duration,
@@ -123,7 +336,7 @@ fn calculate_summary(activities: &[Activity]) -> Summary {
     }
 }
 
-fn format_summary(summary: &Summary) -> String {
+fn format_summary(summary: &Summary, work_target: Option<chrono::Duration>) -> String {
     let mut output = String::new();
     
     // Format the total time line
@@ -150,8 +363,30 @@ fn format_summary(summary: &Summary) -> String {
                 util::format_duration(current_time)));
         }
     }
+
+    // Show progress against the work-hours target, if configured
+    if let Some(target) = work_target {
+        if target > chrono::Duration::zero() {
+            let percent = summary.work_time.num_seconds() as f64
+                / target.num_seconds() as f64 * 100.0;
+
+            if summary.work_time >= target {
+                let over = summary.work_time - target;
+                output.push_str(&format!(" / {} target ({:.0}%, {} over)",
+                    util::format_duration(target),
+                    percent,
+                    util::format_duration(over)));
+            } else {
+                let remaining = target - summary.work_time;
+                output.push_str(&format!(" / {} target ({:.0}%, {} remaining)",
+                    util::format_duration(target),
+                    percent,
+                    util::format_duration(remaining)));
+            }
+        }
+    }
     output.push('\n');
-    
+
     // Format the break time line
     output.push_str("  Break: ");
     output.push_str(&util::format_duration(summary.break_time));
@@ -389,13 +624,247 @@ fn format_details(activities: &[Activity], show_comments: bool) -> String {
     output
 }
 
+/// A single activity row in the JSON report.
+#[derive(Serialize)]
+struct JsonActivity {
+    name: String,
+    project: Option<String>,
+    task: String,
+    start: DateTime<Local>,
+    end: DateTime<Local>,
+    duration_seconds: i64,
+    activity_type: ActivityType,
+    comment: Option<String>,
+    is_current_activity: bool,
+}
+
+/// Per-day aggregate in the JSON report.
+#[derive(Serialize)]
+struct JsonDay {
+    date: NaiveDate,
+    work_seconds: i64,
+    break_seconds: i64,
+    total_seconds: i64,
+}
+
+/// Per-project aggregate in the JSON report (work activities only).
+#[derive(Serialize)]
+struct JsonProject {
+    project: String,
+    duration_seconds: i64,
+    tasks: Vec<String>,
+}
+
+/// Summary totals in the JSON report.
+#[derive(Serialize)]
+struct JsonSummary {
+    total_seconds: i64,
+    work_seconds: i64,
+    break_seconds: i64,
+}
+
+/// Top-level JSON report model.
+#[derive(Serialize)]
+struct JsonReport {
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    summary: JsonSummary,
+    activities: Vec<JsonActivity>,
+    per_day: Vec<JsonDay>,
+    per_project: Vec<JsonProject>,
+}
+
+/// Build the serializable report model (summary, per-activity rows, per-day and
+/// per-project aggregates) shared by the JSON and Markdown output modes.
+fn build_report_model(activities: &[Activity], options: &ReportOptions) -> JsonReport {
+    let filtered_activities: Vec<Activity> = activities.iter()
+        .filter(|a| {
+            if let Some(proj_filter) = &options.project_filter {
+                if let Some(proj) = &a.project {
+                    return proj == proj_filter;
+                }
+                return false;
+            }
+            true
+        })
+        .cloned()
+        .collect();
+
+    // Snap durations to the configured rounding increment so the JSON/Markdown
+    // output stays internally consistent with the text and CSV formats.
+    let rounded_activities = round_activities(&filtered_activities, options);
+
+    let summary = calculate_summary(&rounded_activities);
+
+    let mut sorted_activities = rounded_activities.clone();
+    sorted_activities.sort_by(|a, b| a.start.cmp(&b.start));
+
+    let activity_rows: Vec<JsonActivity> = sorted_activities.iter()
+        .map(|a| JsonActivity {
+            name: a.name.clone(),
+            project: a.project.clone(),
+            task: a.task.clone(),
+            start: a.start,
+            end: a.end,
+            duration_seconds: a.duration.num_seconds(),
+            activity_type: a.activity_type.clone(),
+            comment: a.comment.clone(),
+            is_current_activity: a.is_current_activity,
+        })
+        .collect();
+
+    // Per-day aggregates.
+    let mut days: HashMap<NaiveDate, (chrono::Duration, chrono::Duration)> = HashMap::new();
+    for activity in &rounded_activities {
+        let date = activity.end.date_naive();
+        let entry = days.entry(date).or_insert((chrono::Duration::zero(), chrono::Duration::zero()));
+        match activity.activity_type {
+            ActivityType::Work => entry.0 = entry.0 + activity.duration,
+            ActivityType::Break => entry.1 = entry.1 + activity.duration,
+            ActivityType::Ignored => {},
+        }
+    }
+    let mut day_dates: Vec<NaiveDate> = days.keys().cloned().collect();
+    day_dates.sort();
+    let per_day: Vec<JsonDay> = day_dates.iter()
+        .map(|date| {
+            let (work, brk) = &days[date];
+            JsonDay {
+                date: *date,
+                work_seconds: work.num_seconds(),
+                break_seconds: brk.num_seconds(),
+                total_seconds: (*work + *brk).num_seconds(),
+            }
+        })
+        .collect();
+
+    // Per-project aggregates (work only), reusing the shared grouping.
+    let projects = group_by_project(&rounded_activities);
+    let mut project_names: Vec<&String> = projects.keys().collect();
+    project_names.sort();
+    let per_project: Vec<JsonProject> = project_names.iter()
+        .map(|name| {
+            let (duration, tasks) = &projects[*name];
+            JsonProject {
+                project: (*name).clone(),
+                duration_seconds: duration.num_seconds(),
+                tasks: tasks.clone(),
+            }
+        })
+        .collect();
+
+    JsonReport {
+        start_date: options.range.start_date,
+        end_date: options.range.end_date,
+        summary: JsonSummary {
+            total_seconds: summary.total_time.num_seconds(),
+            work_seconds: summary.work_time.num_seconds(),
+            break_seconds: summary.break_time.num_seconds(),
+        },
+        activities: activity_rows,
+        per_day,
+        per_project,
+    }
+}
+
+/// Serialize the full report model as JSON, giving `--details`-style per-activity
+/// rows plus per-day and per-project aggregates in a machine-readable form.
+pub fn generate_json_report(activities: &[Activity], options: &ReportOptions) -> Result<String> {
+    let report = build_report_model(activities, options);
+    Ok(serde_json::to_string_pretty(&report)?)
+}
+
+/// Render the report model as Markdown tables, suitable for pasting into notes
+/// or wikis.
+pub fn generate_markdown_report(activities: &[Activity], options: &ReportOptions) -> Result<String> {
+    let report = build_report_model(activities, options);
+    let mut output = String::new();
+
+    // Title
+    if report.start_date == report.end_date {
+        output.push_str(&format!("# {}\n\n", util::format_date_title(report.start_date)));
+    } else {
+        output.push_str(&format!(
+            "# {} to {}\n\n",
+            util::format_date_title(report.start_date),
+            util::format_date_title(report.end_date),
+        ));
+    }
+
+    // Summary
+    output.push_str("## Summary\n\n");
+    output.push_str("| Metric | Duration |\n| --- | --- |\n");
+    output.push_str(&format!("| Total | {} |\n", util::format_duration(chrono::Duration::seconds(report.summary.total_seconds))));
+    output.push_str(&format!("| Working | {} |\n", util::format_duration(chrono::Duration::seconds(report.summary.work_seconds))));
+    output.push_str(&format!("| Break | {} |\n\n", util::format_duration(chrono::Duration::seconds(report.summary.break_seconds))));
+
+    // Projects
+    output.push_str("## Projects\n\n");
+    output.push_str("| Project | Duration | Tasks |\n| --- | --- | --- |\n");
+    for project in &report.per_project {
+        let label = if project.project.is_empty() { "(no project)" } else { &project.project };
+        output.push_str(&format!(
+            "| {} | {} | {} |\n",
+            label,
+            util::format_duration(chrono::Duration::seconds(project.duration_seconds)),
+            project.tasks.join(", "),
+        ));
+    }
+    output.push('\n');
+
+    // Activities
+    output.push_str("## Activities\n\n");
+    output.push_str("| Date | Start | End | Type | Project | Task | Duration |\n");
+    output.push_str("| --- | --- | --- | --- | --- | --- | --- |\n");
+    for activity in &report.activities {
+        let activity_type = match activity.activity_type {
+            ActivityType::Work => "Work",
+            ActivityType::Break => "Break",
+            ActivityType::Ignored => "Ignored",
+        };
+        output.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} |\n",
+            activity.start.format("%Y-%m-%d"),
+            activity.start.format("%H:%M"),
+            activity.end.format("%H:%M"),
+            activity_type,
+            activity.project.clone().unwrap_or_default(),
+            activity.task,
+            util::format_duration(chrono::Duration::seconds(activity.duration_seconds)),
+        ));
+    }
+    output.push('\n');
+
+    // Per-day aggregates for multi-day ranges
+    if report.per_day.len() > 1 {
+        output.push_str("## Per day\n\n");
+        output.push_str("| Date | Working | Break | Total |\n| --- | --- | --- | --- |\n");
+        for day in &report.per_day {
+            output.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                day.date,
+                util::format_duration(chrono::Duration::seconds(day.work_seconds)),
+                util::format_duration(chrono::Duration::seconds(day.break_seconds)),
+                util::format_duration(chrono::Duration::seconds(day.total_seconds)),
+            ));
+        }
+        output.push('\n');
+    }
+
+    Ok(output)
+}
+
 pub fn generate_csv_report(activities: &[Activity], options: &ReportOptions) -> Result<String> {
     let csv_section = match options.csv_section.as_deref() {
         Some("per-day") | Some("per_day") => "per_day",
         Some("per-task") | Some("per_task") => "per_task",
         _ => return Err(anyhow::anyhow!("Invalid CSV section: {:?}", options.csv_section)),
     };
-    
+
+    // Apply the configured rounding so CSV totals match the text report.
+    let rounded = round_activities(activities, options);
+    let activities = &rounded;
+
     let mut wtr = csv::WriterBuilder::new().from_writer(vec![]);
     
     if csv_section == "per_day" {
@@ -1,10 +1,11 @@
 use anyhow::{Context, Result};
-use chrono::{DateTime, Local, NaiveDate, TimeZone};
+use chrono::{DateTime, Duration, Local, NaiveDate};
 use std::fs::{self, File, OpenOptions};
 use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
 use std::path::Path;
 
-use crate::entry::{Activity, Entry, HELLO_ENTRY_NAME, MIDNIGHT_SEPARATOR_PREFIX};
+use crate::entry::{Activity, ActivityType, Entry, HELLO_ENTRY_NAME, IDLE_SEPARATOR_PREFIX, MIDNIGHT_SEPARATOR_PREFIX};
+use crate::util::ClockTz;
 
 pub fn read_entries(data_file: &Path) -> Result<Vec<Entry>> {
     if !data_file.exists() {
@@ -51,17 +52,17 @@ pub fn read_entries(data_file: &Path) -> Result<Vec<Entry>> {
     Ok(entries)
 }
 
-pub fn append_entry(data_file: &Path, entry: &Entry) -> Result<()> {
+pub fn append_entry(data_file: &Path, entry: &Entry, clock: &ClockTz) -> Result<()> {
     // Create parent directories if they don't exist
     if let Some(parent) = data_file.parent() {
         fs::create_dir_all(parent)
             .context(format!("Failed to create directory {:?}", parent))?;
     }
-    
+
     // Determine if we need to add a separator line
     let add_separator = if data_file.exists() {
         let entries = read_entries(data_file)?;
-        !entries.is_empty() && entries.last().unwrap().datetime.date_naive() != entry.datetime.date_naive()
+        !entries.is_empty() && clock.today(entries.last().unwrap().datetime) != clock.today(entry.datetime)
     } else {
         false
     };
@@ -111,35 +112,95 @@ pub fn append_entry(data_file: &Path, entry: &Entry) -> Result<()> {
 }
 
 
+/// Rewrite the entire log file from a list of entries, re-emitting the blank
+/// separator line between day boundaries the same way `append_entry` does.
+pub fn write_entries(data_file: &Path, entries: &[Entry], clock: &ClockTz) -> Result<()> {
+    if let Some(parent) = data_file.parent() {
+        fs::create_dir_all(parent)
+            .context(format!("Failed to create directory {:?}", parent))?;
+    }
+
+    let mut file = File::create(data_file)
+        .context(format!("Failed to open data file for writing: {:?}", data_file))?;
+
+    let mut previous_date = None;
+    for entry in entries {
+        let entry_date = clock.today(entry.datetime);
+        if let Some(prev) = previous_date {
+            if prev != entry_date {
+                writeln!(file)?;
+            }
+        }
+        writeln!(file, "{}", entry).context("Failed to write entry to file")?;
+        previous_date = Some(entry_date);
+    }
+
+    Ok(())
+}
+
 pub fn entries_to_activities(
-    entries: &[Entry], 
-    start_date: Option<NaiveDate>, 
+    entries: &[Entry],
+    start_date: Option<NaiveDate>,
     end_date: Option<NaiveDate>,
-    now: Option<DateTime<Local>> // Add parameter for current time
+    now: Option<DateTime<Local>>, // Add parameter for current time
+    idle_threshold: Option<Duration>, // Gap beyond which a span is treated as idle
+    clock: &ClockTz,
 ) -> Vec<Activity> {
     let mut activities = Vec::new();
-    
+
     // We need at least one entry to create an activity
     if entries.is_empty() {
         return activities;
     }
-    
+
     // Create activities from consecutive entries, skipping midnight separators and hello entries
     for i in 0..entries.len() - 1 {
         // Skip current entry if it's a hello entry - hello doesn't create duration
         if entries[i].name == HELLO_ENTRY_NAME {
             continue;
         }
-        
+
         // Skip current entry if it's a midnight separator
         if entries[i].name.starts_with(MIDNIGHT_SEPARATOR_PREFIX) {
             continue;
         }
-        
+
         // Get the start and end times
         let start_time = entries[i].datetime;
         let end_time = entries[i+1].datetime;
-        
+
+        // An explicit idle separator (from the `idle` subcommand) always counts
+        // as a break rather than work, regardless of the following task name.
+        if entries[i].name.starts_with(IDLE_SEPARATOR_PREFIX) {
+            let mut idle = Activity::new(
+                entries[i].name.clone(),
+                start_time,
+                end_time,
+                false,
+                entries[i].comment.clone(),
+            );
+            idle.activity_type = ActivityType::Break;
+            activities.push(idle);
+            continue;
+        }
+
+        // When a gap exceeds the idle threshold, attribute the whole span to an
+        // idle break interval instead of silently inflating the task.
+        if let Some(threshold) = idle_threshold {
+            if end_time.signed_duration_since(start_time) > threshold {
+                let mut idle = Activity::new(
+                    IDLE_SEPARATOR_PREFIX.to_string(),
+                    start_time,
+                    end_time,
+                    false,
+                    None,
+                );
+                idle.activity_type = ActivityType::Break;
+                activities.push(idle);
+                continue;
+            }
+        }
+
         // Create activity using the CURRENT entry's name
         // This represents what you were doing during this time span
         let activity = Activity::new(
@@ -149,7 +210,7 @@ pub fn entries_to_activities(
             false,
             entries[i].comment.clone(),
         );
-        
+
         activities.push(activity);
     }
     
@@ -157,12 +218,13 @@ pub fn entries_to_activities(
     // Only add if we have the current time and it's the same day
     if let Some(now_time) = now {
         if let Some(last_entry) = entries.last() {
-            // Skip if it's a hello entry or midnight separator
-            if last_entry.name != HELLO_ENTRY_NAME && 
-               !last_entry.name.starts_with(MIDNIGHT_SEPARATOR_PREFIX) {
+            // Skip if it's a hello entry, midnight separator or idle separator
+            if last_entry.name != HELLO_ENTRY_NAME &&
+               !last_entry.name.starts_with(MIDNIGHT_SEPARATOR_PREFIX) &&
+               !last_entry.name.starts_with(IDLE_SEPARATOR_PREFIX) {
                 
                 // Only add if it's from today
-                if last_entry.datetime.date_naive() == now_time.date_naive() {
+                if clock.today(last_entry.datetime) == clock.today(now_time) {
                     let activity = Activity::new(
                         last_entry.name.clone(),
                         last_entry.datetime,
@@ -180,49 +242,49 @@ pub fn entries_to_activities(
     // Apply date filtering if specified
     if let (Some(start), Some(end)) = (start_date, end_date) {
         activities.retain(|activity| {
-            let activity_date = activity.end.date_naive();
+            let activity_date = clock.today(activity.end);
             activity_date >= start && activity_date <= end
         });
     }
-    
+
     activities
 }
 
-pub fn filter_entries_by_date_range(entries: &[Entry], start_date: NaiveDate, end_date: NaiveDate) -> Vec<Entry> {
+pub fn filter_entries_by_date_range(entries: &[Entry], start_date: NaiveDate, end_date: NaiveDate, clock: &ClockTz) -> Vec<Entry> {
     // If there are no entries, return an empty vector
     if entries.is_empty() {
         return Vec::new();
     }
-    
+
     let mut filtered_entries = Vec::new();
-    
+
     // Find the last entry before the start date (needed for calculating the first activity's duration)
     // This handles the case where an activity starts before our date range but ends within it
     let mut last_entry_before_range = None;
     for entry in entries.iter().rev() {
-        if entry.datetime.date_naive() < start_date {
+        if clock.today(entry.datetime) < start_date {
             last_entry_before_range = Some(entry.clone());
             break;
         }
     }
-    
+
     // If we found a last entry before the range, include it
     if let Some(entry) = last_entry_before_range {
         filtered_entries.push(entry);
     }
-    
+
     // Include all entries within the date range
     for entry in entries {
-        let entry_date = entry.datetime.date_naive();
-        
+        let entry_date = clock.today(entry.datetime);
+
         if entry_date >= start_date && entry_date <= end_date {
             filtered_entries.push(entry.clone());
         }
     }
-    
+
     // Sort entries by datetime (just in case)
     filtered_entries.sort_by(|a, b| a.datetime.cmp(&b.datetime));
-    
+
     filtered_entries
 }
 
@@ -1,29 +1,163 @@
 use anyhow::{anyhow, Result};
-use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Weekday};
+use chrono::{DateTime, Datelike, Duration, Local, Months, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+
+use crate::config::Config;
+
+/// The wall-clock timezone the CLI operates in. Report date ranges, entry
+/// date filtering, the midnight separator and `--now` parsing are interpreted
+/// against this zone. Timestamps are still written to the log and printed in
+/// reports with the system local offset.
+#[derive(Debug, Clone, Copy)]
+pub enum ClockTz {
+    Local,
+    Zone(Tz),
+}
+
+impl ClockTz {
+    /// Resolve the clock zone from the `--timezone` flag, falling back to the
+    /// configured default when timezone support is enabled, otherwise the
+    /// system local zone.
+    pub fn resolve(flag: Option<&str>, config: &Config) -> Result<ClockTz> {
+        let name = flag.or_else(|| {
+            if config.timezone_enabled {
+                config.timezone.as_deref()
+            } else {
+                None
+            }
+        });
+
+        match name {
+            Some(name) => {
+                let tz: Tz = name
+                    .parse()
+                    .map_err(|_| anyhow!("Unknown timezone: {}", name))?;
+                Ok(ClockTz::Zone(tz))
+            },
+            None => Ok(ClockTz::Local),
+        }
+    }
+
+    /// Convert a naive wall-clock datetime into an absolute instant, resolving
+    /// a DST fold by preferring the earlier of the two instants. A time inside
+    /// a DST gap does not exist and is returned as an error.
+    pub fn from_naive(&self, naive: NaiveDateTime) -> Result<DateTime<Local>> {
+        match self {
+            ClockTz::Local => Local
+                .from_local_datetime(&naive)
+                .earliest()
+                .map(|dt| dt.with_timezone(&Local))
+                .ok_or_else(|| anyhow!("No valid local time for {}", naive)),
+            ClockTz::Zone(tz) => tz
+                .from_local_datetime(&naive)
+                .earliest()
+                .map(|dt| dt.with_timezone(&Local))
+                .ok_or_else(|| anyhow!("No valid time for {} in {}", naive, tz)),
+        }
+    }
+
+    /// The current instant, resolved through this zone rather than assuming
+    /// the system's local zone.
+    pub fn now(&self) -> DateTime<Local> {
+        match self {
+            ClockTz::Local => Local::now(),
+            ClockTz::Zone(tz) => Utc::now().with_timezone(tz).with_timezone(&Local),
+        }
+    }
+
+    /// Resolve the current time, honoring an explicit `--now` override parsed
+    /// as wall-clock time in this zone.
+    pub fn parse_now(&self, now_arg: Option<&str>) -> Result<DateTime<Local>> {
+        match now_arg {
+            Some(time_str) => {
+                let naive = NaiveDateTime::parse_from_str(time_str, "%Y-%m-%d %H:%M")?;
+                self.from_naive(naive)
+            },
+            None => Ok(self.now()),
+        }
+    }
+
+    /// The calendar date of an absolute instant as seen from this zone,
+    /// instead of the system's local calendar day. Report ranges, entry date
+    /// filtering and the midnight separator use it; the per-day groupings in
+    /// the report output (activities, charts, JSON/Markdown and CSV per-day
+    /// rows) use the system local calendar day.
+    pub fn today(&self, at: DateTime<Local>) -> NaiveDate {
+        match self {
+            ClockTz::Local => at.date_naive(),
+            ClockTz::Zone(tz) => at.with_timezone(tz).date_naive(),
+        }
+    }
+}
+
+pub fn parse_date_string(date_str: &str, clock: &ClockTz, now: &DateTime<Local>, is_past: bool) -> Result<NaiveDate> {
+    let today = clock.today(*now);
 
-pub fn parse_date_string(date_str: &str, today: &DateTime<Local>, is_past: bool) -> Result<NaiveDate> {
     // First try to parse as a day name
-    if let Some(date) = parse_day_name(date_str, today.date_naive(), is_past) {
+    if let Some(date) = parse_day_name(date_str, today, is_past) {
         return Ok(date);
     }
-    
+
     // Try to parse as an absolute date
     if let Ok(date) = NaiveDate::parse_from_str(date_str, "%Y-%m-%d") {
         return Ok(date);
     }
-    
+
     // Try to parse as a relative reference like "today" or "yesterday"
     if "today".starts_with(&date_str.to_lowercase()) {
-        return Ok(today.date_naive());
+        return Ok(today);
     }
-    
+
     if "yesterday".starts_with(&date_str.to_lowercase()) {
-        return Ok(today.date_naive().pred_opt().unwrap());
+        return Ok(today.pred_opt().unwrap());
     }
-    
+
+    // Try a relative offset like "3 days ago", "2 weeks ago" or "1m"
+    if let Some(date) = parse_relative_offset(date_str, today) {
+        return Ok(date);
+    }
+
     Err(anyhow!("Invalid date format: {}", date_str))
 }
 
+/// Parse a relative offset such as "3 days ago", "2 weeks ago", "1 month ago"
+/// or the compact `3d` / `2w` / `1m` forms (the trailing "ago" is optional).
+/// The offset is always into the past, consistent with how report dates are
+/// resolved.
+fn parse_relative_offset(date_str: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let mut text = date_str.trim().to_lowercase();
+    if let Some(stripped) = text.strip_suffix("ago") {
+        text = stripped.trim().to_string();
+    }
+    let text = text.trim();
+
+    let digits: String = text.chars().take_while(|c| c.is_ascii_digit()).collect();
+    if digits.is_empty() {
+        return None;
+    }
+
+    let amount: i64 = digits.parse().ok()?;
+    let unit = text[digits.len()..].trim();
+
+    match unit {
+        "d" | "day" | "days" => Some(today - Duration::days(amount)),
+        "w" | "week" | "weeks" => Some(today - Duration::days(amount * 7)),
+        "m" | "month" | "months" => today.checked_sub_months(Months::new(amount as u32)),
+        _ => None,
+    }
+}
+
+/// Expand a bare natural-language range like "last week" or "last month" into
+/// an inclusive `(start, end)` pair, reusing the ISO week and month resolvers.
+/// Returns `None` for anything that isn't a recognised range form.
+pub fn parse_relative_range(date_str: &str, today: NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+    match date_str.trim().to_lowercase().as_str() {
+        "last week" => parse_week("prev", today).ok(),
+        "last month" => parse_month("prev", today).ok(),
+        _ => None,
+    }
+}
+
 pub fn parse_day_name(day_name: &str, today: NaiveDate, is_past: bool) -> Option<NaiveDate> {
     let day = match day_name.to_lowercase().as_str() {
         d if "monday".starts_with(d) => Weekday::Mon,
@@ -49,6 +183,58 @@ pub fn parse_day_name(day_name: &str, today: NaiveDate, is_past: bool) -> Option
     }
 }
 
+/// How a duration is snapped to a rounding increment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundMode {
+    Nearest,
+    Up,
+    Down,
+}
+
+impl RoundMode {
+    /// Parse a round mode from its CLI/config spelling, defaulting unknown or
+    /// empty values to `Nearest`.
+    pub fn parse(value: Option<&str>) -> RoundMode {
+        match value.map(|v| v.to_lowercase()).as_deref() {
+            Some("up") | Some("ceil") => RoundMode::Up,
+            Some("down") | Some("floor") => RoundMode::Down,
+            _ => RoundMode::Nearest,
+        }
+    }
+}
+
+/// Snap a duration to a multiple of `minutes`: the nearest one, or the next
+/// one up or down, depending on `mode`.
+pub fn round_duration(duration: Duration, minutes: i64, mode: RoundMode) -> Duration {
+    if minutes <= 0 {
+        return duration;
+    }
+    let increment = minutes * 60;
+    let secs = duration.num_seconds();
+    let floor = secs.div_euclid(increment) * increment;
+    let remainder = secs.rem_euclid(increment);
+
+    let rounded = match mode {
+        RoundMode::Down => floor,
+        RoundMode::Up => floor + if remainder > 0 { increment } else { 0 },
+        RoundMode::Nearest => {
+            if remainder * 2 >= increment {
+                floor + increment
+            } else {
+                floor
+            }
+        },
+    };
+
+    Duration::seconds(rounded)
+}
+
+/// Round a duration up to the nearest `minutes` increment. Used for billing,
+/// where conventions commonly charge whole 6/15/30-minute blocks.
+pub fn round_duration_up(duration: Duration, minutes: i64) -> Duration {
+    round_duration(duration, minutes, RoundMode::Up)
+}
+
 pub fn format_duration(duration: Duration) -> String {
     let total_seconds = duration.num_seconds();
     let total_minutes = total_seconds / 60;
@@ -57,54 +243,79 @@ pub fn format_duration(duration: Duration) -> String {
     format!("{}h{:02}", hours, minutes)
 }
 
-pub fn beginning_of_day(date: NaiveDate) -> DateTime<Local> {
-    let naive = date.and_hms_opt(0, 0, 0).unwrap();
-    Local.from_utc_datetime(&naive)
-}
-
-pub fn end_of_day(date: NaiveDate) -> DateTime<Local> {
-    let naive = date.and_hms_opt(23, 59, 59).unwrap();
-    Local.from_utc_datetime(&naive)
-}
-
 pub fn parse_week(week_str: &str, today: NaiveDate) -> Result<(NaiveDate, NaiveDate)> {
-    let first_day = match week_str.to_lowercase().as_str() {
+    // Resolve to an (ISO week-year, ISO week) pair, then expand to Monday..Sunday.
+    let (year, week) = match week_str.to_lowercase().as_str() {
         "this" => {
-            // Get the Monday of current week
-            let days_since_monday = today.weekday().num_days_from_monday();
-            today - chrono::Duration::days(days_since_monday as i64)
+            let iso = today.iso_week();
+            (iso.year(), iso.week())
         },
         "prev" | "previous" => {
-            // Get the Monday of previous week
-            let days_since_monday = today.weekday().num_days_from_monday();
-            today - chrono::Duration::days((days_since_monday + 7) as i64)
+            let iso = (today - chrono::Duration::days(7)).iso_week();
+            (iso.year(), iso.week())
         },
-        _ => {
-            // Try to parse as week number
-            match week_str.parse::<i32>() {
-                Ok(week_num) => {
-                    if week_num <= 0 || week_num > 53 {
-                        return Err(anyhow!("Week number must be between 1 and 53"));
-                    }
-                    
-                    let year = today.year();
-                    // This is a simplification - proper ISO week calculation is more complex
-                    let first_day_of_year = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
-                    let days_to_monday = (first_day_of_year.weekday().num_days_from_monday() + 7 - 1) % 7;
-                    let first_monday = first_day_of_year + chrono::Duration::days(days_to_monday as i64);
-                    
-                    first_monday + chrono::Duration::days((week_num - 1) as i64 * 7)
-                },
-                Err(_) => return Err(anyhow!("Invalid week format: {}", week_str)),
-            }
-        }
+        _ => parse_week_spec(week_str, today.iso_week().year())?,
     };
-    
+
+    let first_day = iso_week_monday(year, week)?;
     let last_day = first_day + chrono::Duration::days(6); // Sunday
-    
+
     Ok((first_day, last_day))
 }
 
+/// Parse an explicit week spec, either a bare `NN` (using `default_year` as the
+/// ISO week-year) or the fully qualified `YYYY-Www` form, rejecting a week
+/// beyond the 52 or 53 weeks the target ISO year actually has.
+fn parse_week_spec(week_str: &str, default_year: i32) -> Result<(i32, u32)> {
+    let lower = week_str.to_lowercase();
+    let (year, week) = if let Some((year_str, week_num)) = lower.split_once("-w") {
+        let year = year_str.parse::<i32>()
+            .map_err(|_| anyhow!("Invalid week format: {}", week_str))?;
+        let week = week_num.parse::<u32>()
+            .map_err(|_| anyhow!("Invalid week format: {}", week_str))?;
+        (year, week)
+    } else {
+        let week = week_str.parse::<u32>()
+            .map_err(|_| anyhow!("Invalid week format: {}", week_str))?;
+        (default_year, week)
+    };
+
+    let max_week = weeks_in_iso_year(year);
+    if week < 1 || week > max_week {
+        return Err(anyhow!("Week number must be between 1 and {} for {}", max_week, year));
+    }
+
+    Ok((year, week))
+}
+
+/// Monday of the given ISO week-year / week.
+fn iso_week_monday(year: i32, week: u32) -> Result<NaiveDate> {
+    NaiveDate::from_isoywd_opt(year, week, Weekday::Mon)
+        .ok_or_else(|| anyhow!("Invalid ISO week: {}-W{:02}", year, week))
+}
+
+/// Number of ISO weeks in a given ISO week-year (52 or 53).
+fn weeks_in_iso_year(year: i32) -> u32 {
+    if NaiveDate::from_isoywd_opt(year, 53, Weekday::Mon).is_some() {
+        53
+    } else {
+        52
+    }
+}
+
+/// Count the working days (Monday–Friday) in an inclusive date range.
+pub fn count_working_days(start: NaiveDate, end: NaiveDate) -> i64 {
+    let mut count = 0;
+    let mut date = start;
+    while date <= end {
+        if !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            count += 1;
+        }
+        date += Duration::days(1);
+    }
+    count
+}
+
 pub fn parse_month(month_str: &str, today: NaiveDate) -> Result<(NaiveDate, NaiveDate)> {
     let (year, month) = match month_str.to_lowercase().as_str() {
         "this" => (today.year(), today.month()),
@@ -181,16 +392,6 @@ pub fn parse_month(month_str: &str, today: NaiveDate) -> Result<(NaiveDate, Naiv
     Ok((first_day, last_day))
 }
 
-pub fn parse_now_arg(now_arg: Option<&str>) -> Result<DateTime<Local>> {
-    match now_arg {
-        Some(time_str) => {
-            let naive = NaiveDateTime::parse_from_str(time_str, "%Y-%m-%d %H:%M")?;
-            Ok(Local.from_utc_datetime(&naive))
-        },
-        None => Ok(Local::now()),
-    }
-}
-
 pub fn format_date_title(date: NaiveDate) -> String {
     let weekday = match date.weekday() {
         Weekday::Mon => "Monday",
@@ -227,3 +428,88 @@ pub fn format_date_title(date: NaiveDate) -> String {
 pub fn format_title(text: &str) -> String {
     format!("{:-^80}", format!(" {} ", text))
 }
+
+/// Detect the current terminal width in columns, falling back to 80 when the
+/// output isn't a terminal (e.g. piped to a file).
+pub fn terminal_width() -> usize {
+    terminal_size::terminal_size()
+        .map(|(terminal_size::Width(w), _)| w as usize)
+        .unwrap_or(80)
+}
+
+/// Render a horizontal bar of the given fraction (0.0..=1.0) of `max_width`
+/// columns using Unicode block glyphs, resolving the sub-cell remainder with
+/// eighth-width partial blocks.
+pub fn render_bar(fraction: f64, max_width: usize) -> String {
+    const EIGHTHS: [char; 8] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+
+    let fraction = fraction.clamp(0.0, 1.0);
+    let eighths = (fraction * max_width as f64 * 8.0).round() as usize;
+    let full = eighths / 8;
+    let remainder = eighths % 8;
+
+    let mut bar = "█".repeat(full);
+    if remainder > 0 {
+        bar.push(EIGHTHS[remainder - 1]);
+    }
+
+    bar
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_week_handles_iso_year_boundary() {
+        // 2022-01-01 is a Saturday, which ISO-8601 assigns to week 52 of the
+        // *2021* week-year, not week 1 of 2022.
+        let start_of_2022 = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+
+        let (start, end) = parse_week("this", start_of_2022).unwrap();
+        assert_eq!(start, NaiveDate::from_ymd_opt(2021, 12, 27).unwrap());
+        assert_eq!(end, NaiveDate::from_ymd_opt(2022, 1, 2).unwrap());
+
+        let (start, end) = parse_week("2021-W52", start_of_2022).unwrap();
+        assert_eq!(start, NaiveDate::from_ymd_opt(2021, 12, 27).unwrap());
+        assert_eq!(end, NaiveDate::from_ymd_opt(2022, 1, 2).unwrap());
+
+        let (start, end) = parse_week("2022-W01", start_of_2022).unwrap();
+        assert_eq!(start, NaiveDate::from_ymd_opt(2022, 1, 3).unwrap());
+        assert_eq!(end, NaiveDate::from_ymd_opt(2022, 1, 9).unwrap());
+    }
+
+    #[test]
+    fn parse_week_rejects_weeks_beyond_iso_year() {
+        // 2020 is a 53-week ISO year; 2021 only has 52.
+        let today = NaiveDate::from_ymd_opt(2020, 6, 1).unwrap();
+        assert!(parse_week("2020-W53", today).is_ok());
+        assert!(parse_week("2021-W53", today).is_err());
+    }
+
+    #[test]
+    fn from_naive_resolves_fall_back_to_earliest_offset() {
+        // Clocks in America/New_York fall back from 02:00 EDT to 01:00 EST on
+        // 2023-11-05, so 01:30 occurs twice; we should pick the earlier (EDT,
+        // UTC-4) occurrence rather than erroring or silently picking the
+        // later (EST, UTC-5) one.
+        let tz: Tz = "America/New_York".parse().unwrap();
+        let clock = ClockTz::Zone(tz);
+        let naive = NaiveDate::from_ymd_opt(2023, 11, 5).unwrap().and_hms_opt(1, 30, 0).unwrap();
+
+        let resolved = clock.from_naive(naive).unwrap();
+        let expected_utc = NaiveDate::from_ymd_opt(2023, 11, 5).unwrap().and_hms_opt(5, 30, 0).unwrap();
+        assert_eq!(resolved.naive_utc(), expected_utc);
+    }
+
+    #[test]
+    fn from_naive_rejects_spring_forward_gap() {
+        // Clocks in America/New_York spring forward from 02:00 to 03:00 on
+        // 2023-03-12, so 02:30 never occurs and must be rejected.
+        let tz: Tz = "America/New_York".parse().unwrap();
+        let clock = ClockTz::Zone(tz);
+        let naive = NaiveDate::from_ymd_opt(2023, 3, 12).unwrap().and_hms_opt(2, 30, 0).unwrap();
+
+        assert!(clock.from_naive(naive).is_err());
+    }
+}